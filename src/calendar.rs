@@ -0,0 +1,271 @@
+//! Resolves concrete calendar dates against the rotating cycle `Day`s.
+
+use crate::{DateDayMapping, Day, Holiday, Notice};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+fn day_pred(day: Day) -> Day {
+    use Day::*;
+    match day {
+        A => E,
+        B => A,
+        C => B,
+        D => C,
+        E => D,
+    }
+}
+
+/// Maps concrete dates to rotating cycle `Day`s and cycle numbers.
+///
+/// Anchored at the first working day of a semester (a `NaiveDate` together
+/// with the `Day` and cycle number it corresponds to), a `Calendar` walks
+/// dates forward or backward from that anchor, skipping weekends, `Holiday`s
+/// and `Notice::ClassOff` notices with `day_off: true` so those dates never
+/// consume a cycle `Day` slot.
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    anchor_date: NaiveDate,
+    anchor_day: Day,
+    anchor_cycle: u8,
+    weekend: [Weekday; 2],
+    holidays: Vec<Holiday>,
+    offs: Vec<Notice>,
+}
+
+impl Calendar {
+    /// Create a `Calendar` anchored at `anchor_date`, which is `anchor_day`
+    /// of `anchor_cycle`. `weekend` names the two weekdays RUET observes as
+    /// weekend (Friday/Saturday). `offs` may contain any `Notice`; only
+    /// `Notice::ClassOff { day_off: true, .. }` entries are kept.
+    pub fn new(
+        anchor_date: NaiveDate,
+        anchor_day: Day,
+        anchor_cycle: u8,
+        weekend: [Weekday; 2],
+        holidays: Vec<Holiday>,
+        offs: Vec<Notice>,
+    ) -> Calendar {
+        Calendar {
+            anchor_date,
+            anchor_day,
+            anchor_cycle,
+            weekend,
+            holidays,
+            offs: offs
+                .into_iter()
+                .filter(|notice| matches!(notice, Notice::ClassOff { day_off: true, .. }))
+                .collect(),
+        }
+    }
+
+    fn is_weekend(&self, date: NaiveDate) -> bool {
+        let weekday = date.weekday();
+        weekday == self.weekend[0] || weekday == self.weekend[1]
+    }
+
+    fn holiday_on(&self, date: NaiveDate) -> Option<Holiday> {
+        self.holidays
+            .iter()
+            .find(|holiday| holiday.contains(date))
+            .cloned()
+    }
+
+    fn off_on(&self, date: NaiveDate) -> Option<Notice> {
+        self.offs
+            .iter()
+            .find(|notice| matches!(notice, Notice::ClassOff { date: d, .. } if *d == date))
+            .cloned()
+    }
+
+    fn is_working_day(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && self.holiday_on(date).is_none() && self.off_on(date).is_none()
+    }
+
+    /// Walk from the anchor to `date`, returning the rotated `Day` and cycle
+    /// number `date` would have if it is a working day.
+    fn position_at(&self, date: NaiveDate) -> (Day, u8) {
+        let mut day = self.anchor_day;
+        let mut cycle = self.anchor_cycle;
+
+        if date >= self.anchor_date {
+            let mut cur = self.anchor_date;
+            while cur < date {
+                cur += Duration::days(1);
+                if self.is_working_day(cur) {
+                    let was_e = day == Day::E;
+                    day = day.succ();
+                    if was_e {
+                        cycle += 1;
+                    }
+                }
+            }
+        } else {
+            let mut cur = self.anchor_date;
+            while cur > date {
+                let prev = cur - Duration::days(1);
+                if self.is_working_day(prev) {
+                    let was_a = day == Day::A;
+                    day = day_pred(day);
+                    if was_a {
+                        // Cycles are 1-indexed; clamp instead of underflowing
+                        // past cycle 1 when walking before the semester start.
+                        cycle = cycle.saturating_sub(1).max(1);
+                    }
+                }
+                cur = prev;
+            }
+        }
+
+        (day, cycle)
+    }
+
+    /// Resolve both the `DateDayMapping` and cycle number for `date` in one
+    /// pass, so callers that need both (like `CalendarIter`) don't pay the
+    /// anchor-to-date walk in `position_at` twice.
+    fn resolve(&self, date: NaiveDate) -> (DateDayMapping, u8) {
+        let (day, cycle) = self.position_at(date);
+        let mapping = if self.is_weekend(date) {
+            DateDayMapping::Weekend
+        } else if let Some(holiday) = self.holiday_on(date) {
+            DateDayMapping::Holiday(holiday)
+        } else if let Some(notice) = self.off_on(date) {
+            DateDayMapping::OffDay(notice)
+        } else {
+            DateDayMapping::Day(day)
+        };
+        (mapping, cycle)
+    }
+
+    /// Resolve what `date` maps to: a weekend, a holiday, a notified off day
+    /// or a regular cycle `Day`.
+    pub fn mapping(&self, date: NaiveDate) -> DateDayMapping {
+        self.resolve(date).0
+    }
+
+    /// Resolve the cycle number `date` falls into, regardless of whether
+    /// `date` itself is a working day.
+    pub fn cycle(&self, date: NaiveDate) -> u8 {
+        self.resolve(date).1
+    }
+
+    /// Iterate `(date, mapping, cycle)` for every date in the inclusive
+    /// range `range.0..=range.1`, so downstream code can build agendas.
+    pub fn iter_range(&self, range: (NaiveDate, NaiveDate)) -> CalendarIter<'_> {
+        CalendarIter {
+            calendar: self,
+            cur: range.0,
+            end: range.1,
+        }
+    }
+}
+
+/// Iterator over resolved `(NaiveDate, DateDayMapping, cycle)` triples
+/// produced by [`Calendar::iter_range`].
+#[derive(Debug)]
+pub struct CalendarIter<'a> {
+    calendar: &'a Calendar,
+    cur: NaiveDate,
+    end: NaiveDate,
+}
+
+impl<'a> Iterator for CalendarIter<'a> {
+    type Item = (NaiveDate, DateDayMapping, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cur > self.end {
+            return None;
+        }
+        let date = self.cur;
+        self.cur += Duration::days(1);
+        let (mapping, cycle) = self.calendar.resolve(date);
+        Some((date, mapping, cycle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HolidaySpan;
+
+    // Anchored at 2024-01-01 (a Monday), Day A, cycle 1. Fri/Sat are weekend.
+    fn calendar() -> Calendar {
+        Calendar::new(
+            NaiveDate::from_ymd(2024, 1, 1),
+            Day::A,
+            1,
+            [Weekday::Fri, Weekday::Sat],
+            vec![Holiday {
+                r#for: "Test Holiday".to_owned(),
+                span: HolidaySpan::SingleDay {
+                    on: NaiveDate::from_ymd(2024, 1, 3),
+                },
+                recurrence: None,
+            }],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn walks_forward_across_a_holiday_and_a_weekend() {
+        let cal = calendar();
+
+        // 01-02 Tue: Day B.
+        assert!(matches!(
+            cal.mapping(NaiveDate::from_ymd(2024, 1, 2)),
+            DateDayMapping::Day(Day::B)
+        ));
+        // 01-03 Wed: holiday, doesn't consume a Day slot.
+        assert!(matches!(
+            cal.mapping(NaiveDate::from_ymd(2024, 1, 3)),
+            DateDayMapping::Holiday(_)
+        ));
+        // 01-04 Thu: Day C, continuing the rotation past the holiday.
+        assert!(matches!(
+            cal.mapping(NaiveDate::from_ymd(2024, 1, 4)),
+            DateDayMapping::Day(Day::C)
+        ));
+        // 01-05/06 Fri/Sat: weekend.
+        assert!(matches!(
+            cal.mapping(NaiveDate::from_ymd(2024, 1, 5)),
+            DateDayMapping::Weekend
+        ));
+        assert!(matches!(
+            cal.mapping(NaiveDate::from_ymd(2024, 1, 6)),
+            DateDayMapping::Weekend
+        ));
+        // 01-07 Sun: Day D, cycle unchanged since no E->A wrap happened yet.
+        assert!(matches!(
+            cal.mapping(NaiveDate::from_ymd(2024, 1, 7)),
+            DateDayMapping::Day(Day::D)
+        ));
+        assert_eq!(cal.cycle(NaiveDate::from_ymd(2024, 1, 7)), 1);
+    }
+
+    #[test]
+    fn walks_backward_before_the_anchor_without_underflowing_the_cycle() {
+        let cal = calendar();
+
+        // 2023-12-31 is the working day right before the anchor: rotating
+        // back from Day A lands on Day E, and since cycles are 1-indexed the
+        // cycle number must clamp at 1 instead of wrapping/underflowing.
+        assert!(matches!(
+            cal.mapping(NaiveDate::from_ymd(2023, 12, 31)),
+            DateDayMapping::Day(Day::E)
+        ));
+        assert_eq!(cal.cycle(NaiveDate::from_ymd(2023, 12, 31)), 1);
+
+        // Further back still must stay clamped, not underflow.
+        assert_eq!(cal.cycle(NaiveDate::from_ymd(2023, 12, 1)), 1);
+    }
+
+    #[test]
+    fn iter_range_agrees_with_mapping_and_cycle() {
+        let cal = calendar();
+        let start = NaiveDate::from_ymd(2024, 1, 1);
+        let end = NaiveDate::from_ymd(2024, 1, 10);
+
+        for (date, mapping, cycle) in cal.iter_range((start, end)) {
+            assert_eq!(format!("{:?}", mapping), format!("{:?}", cal.mapping(date)));
+            assert_eq!(cycle, cal.cycle(date));
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! A data-driven course catalog, replacing hardcoded course names.
+
+use crate::{errors::*, ClassRoutine, Department, Notice};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// One course's entry in a `CourseCatalog`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseInfo {
+    /// Full official name of the course.
+    pub official_name: String,
+
+    /// Short, commonly-used name of the course.
+    pub colloquial_name: String,
+
+    /// Credit hours the course is worth.
+    pub credit_hours: f32,
+
+    /// Contact hours per week, unless overridden per-class.
+    pub default_contact_hours: u8,
+}
+
+/// A department-maintained catalog fragment loaded from a serde source
+/// (YAML/JSON), keyed by `Department` then course code.
+pub type CatalogFragment = HashMap<Department, HashMap<String, CourseInfo>>;
+
+fn department_of_code(code: &str) -> Option<Department> {
+    use Department::*;
+    let all = [
+        CE, EEE, ME, CSE, ETE, IPE, GCE, URP, MTE, Arch, ECE, CFPE, BECM, MSE, Chem, Math, Phy, Hum,
+    ];
+    // Course codes appear both with and without a space before the number
+    // (e.g. "EEE 2104" and "EEE2104"), so split on the letter/digit boundary
+    // rather than on whitespace.
+    let prefix: String = code.chars().take_while(|c| c.is_alphabetic()).collect();
+    all.iter().copied().find(|department| department.to_string() == prefix)
+}
+
+/// Canonicalize a course code so `"EEE 2104"` and `"EEE2104"` are the same
+/// catalog entry, regardless of which spelling a fragment or a routine uses.
+fn normalize_code(code: &str) -> String {
+    code.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// A course catalog, keyed by `Department` then course code.
+#[derive(Debug, Clone, Default)]
+pub struct CourseCatalog {
+    departments: HashMap<Department, HashMap<String, CourseInfo>>,
+}
+
+impl CourseCatalog {
+    /// Look up a course's catalog entry.
+    pub fn get(&self, department: Department, code: &str) -> Result<&CourseInfo> {
+        self.departments
+            .get(&department)
+            .and_then(|courses| courses.get(&normalize_code(code)))
+            .ok_or_else(|| format!("No course '{}' available for {}", code, department).into())
+    }
+
+    /// Validate that `code` exists in this catalog and belongs to the
+    /// department parsed from its own prefix (e.g. `"EEE 2104"` -> `EEE`).
+    pub fn validate_code(&self, code: &str) -> Result<&CourseInfo> {
+        let department =
+            department_of_code(code).ok_or_else(|| format!("Can't determine department for course '{}'", code))?;
+        self.get(department, code)
+    }
+
+    /// Validate every `ClassInRoutine.course` in `routine`.
+    pub fn validate_routine(&self, routine: &ClassRoutine) -> Result<()> {
+        for classes in routine.values() {
+            for class in classes {
+                self.validate_code(&class.course)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate every `Notice::ClassTest`/`Notice::Exam` course code in
+    /// `notices`.
+    pub fn validate_notices(&self, notices: &[Notice]) -> Result<()> {
+        for notice in notices {
+            match notice {
+                Notice::ClassTest { course, .. } | Notice::Exam { course, .. } => {
+                    self.validate_code(course)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds a `CourseCatalog` by merging multiple department-maintained
+/// catalog fragments, reporting conflicting definitions.
+#[derive(Debug, Clone, Default)]
+pub struct CourseCatalogBuilder {
+    departments: HashMap<Department, HashMap<String, CourseInfo>>,
+}
+
+impl CourseCatalogBuilder {
+    /// Create an empty builder.
+    pub fn new() -> CourseCatalogBuilder {
+        CourseCatalogBuilder::default()
+    }
+
+    /// Merge in a catalog fragment, erroring if it redefines a course code
+    /// that's already present with a different `CourseInfo`.
+    pub fn merge(mut self, fragment: CatalogFragment) -> Result<CourseCatalogBuilder> {
+        for (department, courses) in fragment {
+            let existing = self.departments.entry(department).or_default();
+            for (code, info) in courses {
+                let code = normalize_code(&code);
+                if let Some(previous) = existing.get(&code) {
+                    if *previous != info {
+                        return Err(format!(
+                            "Conflicting definitions for course '{}' in {}",
+                            code, department
+                        )
+                        .into());
+                    }
+                }
+                existing.insert(code, info);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Finalize the catalog.
+    pub fn build(self) -> CourseCatalog {
+        CourseCatalog {
+            departments: self.departments,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> CourseCatalog {
+        let mut courses = HashMap::new();
+        courses.insert(
+            "EEE 2104".to_owned(),
+            CourseInfo {
+                official_name: "Electrical Circuits".to_owned(),
+                colloquial_name: "Circuits".to_owned(),
+                credit_hours: 3.0,
+                default_contact_hours: 3,
+            },
+        );
+        let mut departments = HashMap::new();
+        departments.insert(Department::EEE, courses);
+
+        CourseCatalogBuilder::new()
+            .merge(departments)
+            .unwrap()
+            .build()
+    }
+
+    #[test]
+    fn validates_the_spaced_code_spelling() {
+        assert!(catalog().validate_code("EEE 2104").is_ok());
+    }
+
+    #[test]
+    fn validates_the_unspaced_code_spelling() {
+        // The crate's own routine fixtures (see the "should print a routine"
+        // test in lib.rs) use this no-space spelling.
+        assert!(catalog().validate_code("EEE2104").is_ok());
+    }
+
+    #[test]
+    fn rejects_an_unknown_course() {
+        assert!(catalog().validate_code("EEE 9999").is_err());
+    }
+
+    #[test]
+    fn merge_reports_conflicting_definitions() {
+        let mut first = HashMap::new();
+        first.insert(
+            "EEE 2104".to_owned(),
+            CourseInfo {
+                official_name: "Electrical Circuits".to_owned(),
+                colloquial_name: "Circuits".to_owned(),
+                credit_hours: 3.0,
+                default_contact_hours: 3,
+            },
+        );
+        let mut second = HashMap::new();
+        second.insert(
+            "EEE 2104".to_owned(),
+            CourseInfo {
+                official_name: "Electrical Circuits II".to_owned(),
+                colloquial_name: "Circuits".to_owned(),
+                credit_hours: 3.0,
+                default_contact_hours: 3,
+            },
+        );
+        let mut first_fragment = HashMap::new();
+        first_fragment.insert(Department::EEE, first);
+        let mut second_fragment = HashMap::new();
+        second_fragment.insert(Department::EEE, second);
+
+        let result = CourseCatalogBuilder::new()
+            .merge(first_fragment)
+            .unwrap()
+            .merge(second_fragment);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,324 @@
+//! Render a `ClassRoutine` as a standalone HTML weekly timetable.
+
+use crate::{ClassFrequency, ClassInRoutine, ClassRoutine, DateDayMapping, Day, Notice, Roll, Thirty};
+use chrono::NaiveDate;
+use std::fmt::Write as _;
+
+const STYLE: &str = r#"
+body { font-family: sans-serif; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border: 1px solid #ccc; padding: 0.5em; vertical-align: top; }
+td.off { background: #eee; }
+div.class-test { color: #a33; font-weight: bold; }
+div.extra-class { color: #388; font-style: italic; }
+li.normal::before { content: "\25A0 "; }
+li.class-test::before { content: "\25A0 "; color: #a33; }
+li.extra-class::before { content: "\25A0 "; color: #388; }
+li.off::before { content: "\25A0 "; color: #888; }
+"#;
+
+/// How a routine cell is annotated/colored in the rendered grid.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CellTag {
+    /// A regular class.
+    Normal,
+
+    /// A `Notice::ClassTest` is scheduled in this slot.
+    ClassTest,
+
+    /// A `Notice::ExtraClass` is scheduled in this slot.
+    ExtraClass,
+
+    /// The whole day is off, for a holiday or a `Notice::ClassOff`.
+    Off,
+}
+
+impl CellTag {
+    fn css_class(self) -> &'static str {
+        match self {
+            CellTag::Normal => "normal",
+            CellTag::ClassTest => "class-test",
+            CellTag::ExtraClass => "extra-class",
+            CellTag::Off => "off",
+        }
+    }
+
+    fn legend_label(self) -> &'static str {
+        match self {
+            CellTag::Normal => "Regular class",
+            CellTag::ClassTest => "Class test",
+            CellTag::ExtraClass => "Extra class",
+            CellTag::Off => "Off / holiday",
+        }
+    }
+}
+
+fn day_label(day: Day) -> &'static str {
+    use Day::*;
+    match day {
+        A => "Day A",
+        B => "Day B",
+        C => "Day C",
+        D => "Day D",
+        E => "Day E",
+    }
+}
+
+/// Describe a `ClassFrequency` as the short marker it should carry in a
+/// rendered cell (e.g. "odd cycles", "Thirty 1 only"), so the same grid
+/// communicates frequency without duplicating cells per cycle.
+fn frequency_marker(frequency: ClassFrequency) -> Option<String> {
+    use ClassFrequency::*;
+    match frequency {
+        EveryCycleWithAll => None,
+        EveryCycleWith(thirty) => Some(format!("Thirty {} only", thirty.0)),
+        OddCyclesWithAll => Some("odd cycles".to_owned()),
+        EvenCyclesWithAll => Some("even cycles".to_owned()),
+        OddCyclesWith(thirty) => Some(format!("odd cycles, Thirty {}", thirty.0)),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn has_class_test(notices: &[Notice], day: Day, period: u8, filter_cycle: Option<u8>) -> bool {
+    notices.iter().any(|notice| {
+        matches!(
+            notice,
+            Notice::ClassTest { day: d, period: p, cycle: c, .. }
+                if *d == day && *p == period && filter_cycle.is_none_or(|cycle| cycle == *c)
+        )
+    })
+}
+
+/// Resolve the `Notice::ExtraClass` entries that land on `day`, using
+/// `resolved` (e.g. `Calendar::iter_range` collected to a slice) to turn each
+/// notice's concrete `date` into the cycle `Day` it falls on. When `filter`
+/// carries a cycle, only extra classes resolving to that same cycle are
+/// returned, matching the cycle the rest of the grid is filtered to.
+fn extra_classes_on<'a>(
+    notices: &'a [Notice],
+    resolved: &[(NaiveDate, DateDayMapping, u8)],
+    day: Day,
+    filter_cycle: Option<u8>,
+) -> Vec<&'a Notice> {
+    notices
+        .iter()
+        .filter(|notice| {
+            let Notice::ExtraClass { date, .. } = notice else {
+                return false;
+            };
+            resolved.iter().any(|(d, mapping, cycle)| {
+                d == date
+                    && matches!(mapping, DateDayMapping::Day(mapped_day) if *mapped_day == day)
+                    && filter_cycle.is_none_or(|filter| filter == *cycle)
+            })
+        })
+        .collect()
+}
+
+fn for_whom_label(scope: crate::WhoScope) -> String {
+    let mut parts = Vec::new();
+    if let Some(section) = scope.section {
+        parts.push(format!("Section {}", section));
+    }
+    if scope.thirty != Thirty(0) {
+        parts.push(format!("Thirty {}", scope.thirty.0));
+    }
+    if parts.is_empty() {
+        "Everyone".to_owned()
+    } else {
+        parts.join(", ")
+    }
+}
+
+/// Render `routine` as a self-contained HTML page, laid out as a grid of
+/// `Day` rows by period columns.
+///
+/// `filter` optionally restricts cells to classes that would sit for a
+/// `Roll` on a cycle, via `ClassInRoutine::would_sit_for`; the cycle is also
+/// used to pick out the matching `Notice::ClassTest`s. `off_days` names the
+/// `Day`s that are off for the rendered cycle (as already resolved from
+/// holidays/`Notice::ClassOff` elsewhere, e.g. by `calendar::Calendar`).
+///
+/// `Notice::ExtraClass` entries carry a concrete date rather than a cycle
+/// `Day`, so placing one in the grid means resolving which `Day` its date
+/// falls on; `resolved` supplies that resolution as `(date, mapping, cycle)`
+/// triples, the same shape `calendar::Calendar::iter_range` yields (pass an
+/// empty slice to omit extra classes from the grid entirely). Each `Day`
+/// row gets a trailing "Extra" column for the `Notice::ExtraClass`es that
+/// resolve onto it.
+pub fn render(
+    routine: &ClassRoutine,
+    filter: Option<(Roll, u8)>,
+    notices: &[Notice],
+    off_days: &[Day],
+    resolved: &[(NaiveDate, DateDayMapping, u8)],
+) -> String {
+    let cycle = filter.map(|(_, cycle)| cycle);
+    let days = [Day::A, Day::B, Day::C, Day::D, Day::E];
+    let mut periods: Vec<u8> = routine.values().flatten().map(|class| class.period).collect();
+    periods.sort_unstable();
+    periods.dedup();
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>Class Routine</title>\n<style>");
+    out.push_str(STYLE);
+    out.push_str("</style>\n</head>\n<body>\n<table>\n<thead>\n<tr><th>Day</th>");
+    for period in &periods {
+        let _ = write!(out, "<th>Period {}</th>", period);
+    }
+    out.push_str("<th>Extra</th>");
+    out.push_str("</tr>\n</thead>\n<tbody>\n");
+
+    for day in &days {
+        let _ = write!(out, "<tr><th>{}</th>", day_label(*day));
+        let off = off_days.contains(day);
+        let empty: Vec<ClassInRoutine> = Vec::new();
+        let classes = routine.get(day).unwrap_or(&empty);
+
+        for period in &periods {
+            if off {
+                out.push_str("<td class=\"off\"></td>");
+                continue;
+            }
+
+            let cell_classes: Vec<&ClassInRoutine> = classes
+                .iter()
+                .filter(|class| class.period == *period)
+                .filter(|class| filter.is_none_or(|(roll, cycle)| class.would_sit_for(roll, cycle)))
+                .collect();
+
+            out.push_str("<td>");
+            for class in cell_classes {
+                let tag = if has_class_test(notices, *day, *period, cycle) {
+                    CellTag::ClassTest
+                } else {
+                    CellTag::Normal
+                };
+                let _ = write!(
+                    out,
+                    "<div class=\"{}\"><strong>{}</strong><br>{}<br>{}",
+                    tag.css_class(),
+                    escape(&class.course),
+                    escape(&class.teacher),
+                    escape(&class.class_room),
+                );
+                if let Some(marker) = frequency_marker(class.frequency) {
+                    let _ = write!(out, "<br><em>{}</em>", escape(&marker));
+                }
+                out.push_str("</div>");
+            }
+            out.push_str("</td>");
+        }
+
+        out.push_str("<td>");
+        if !off {
+            for notice in extra_classes_on(notices, resolved, *day, cycle) {
+                if let Notice::ExtraClass { time, for_whom, .. } = notice {
+                    let _ = write!(
+                        out,
+                        "<div class=\"{}\">{} ({})</div>",
+                        CellTag::ExtraClass.css_class(),
+                        escape(&time.format("%H:%M").to_string()),
+                        escape(&for_whom_label(*for_whom)),
+                    );
+                }
+            }
+        }
+        out.push_str("</td>");
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody>\n</table>\n");
+
+    out.push_str("<h2>Legend</h2>\n<ul>\n");
+    for tag in &[CellTag::Normal, CellTag::ClassTest, CellTag::ExtraClass, CellTag::Off] {
+        let _ = writeln!(out, "<li class=\"{}\">{}</li>", tag.css_class(), tag.legend_label());
+    }
+    out.push_str("</ul>\n");
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClassFrequency, WhoScope};
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+
+    fn class(course: &str, teacher: &str, class_room: &str, period: u8) -> ClassInRoutine {
+        ClassInRoutine {
+            course: course.to_owned(),
+            teacher: teacher.to_owned(),
+            period,
+            class_room: class_room.to_owned(),
+            contact_hours: 1,
+            frequency: ClassFrequency::EveryCycleWithAll,
+            comment: String::new(),
+        }
+    }
+
+    #[test]
+    fn escapes_course_teacher_and_room_in_the_rendered_cell() {
+        let mut routine = HashMap::new();
+        routine.insert(Day::A, vec![class("EEE<2104>", "X & Y", "Room <1>", 1)]);
+
+        let out = render(&routine, None, &[], &[], &[]);
+
+        assert!(!out.contains("EEE<2104>"));
+        assert!(out.contains("EEE&lt;2104&gt;"));
+        assert!(out.contains("X &amp; Y"));
+        assert!(out.contains("Room &lt;1&gt;"));
+    }
+
+    #[test]
+    fn off_day_renders_an_empty_cell_with_the_off_class() {
+        let mut routine = HashMap::new();
+        routine.insert(Day::A, vec![class("EEE 2104", "Karim", "201", 1)]);
+
+        let out = render(&routine, None, &[], &[Day::A], &[]);
+
+        assert!(out.contains("<td class=\"off\"></td>"));
+        assert!(!out.contains("EEE 2104"));
+    }
+
+    #[test]
+    fn a_matching_class_test_notice_tags_the_cell_as_class_test() {
+        let mut routine = HashMap::new();
+        routine.insert(Day::A, vec![class("EEE 2104", "Karim", "201", 1)]);
+        let notices = vec![Notice::ClassTest {
+            day: Day::A,
+            cycle: 1,
+            period: 1,
+            course: "EEE 2104".to_owned(),
+            teacher: "Karim".to_owned(),
+            extra_info: String::new(),
+        }];
+
+        let out = render(&routine, None, &notices, &[], &[]);
+
+        assert!(out.contains("<div class=\"class-test\">"));
+        assert!(!out.contains("<div class=\"normal\">"));
+    }
+
+    #[test]
+    fn an_extra_class_resolved_onto_a_day_is_placed_in_its_extra_column() {
+        let routine = HashMap::new();
+        let date = NaiveDate::from_ymd(2024, 1, 2);
+        let time = chrono::NaiveDateTime::new(date, chrono::NaiveTime::from_hms(10, 0, 0));
+        let notices = vec![Notice::ExtraClass {
+            date,
+            time: chrono::Local.from_utc_datetime(&time),
+            for_whom: WhoScope::default(),
+        }];
+        let resolved = vec![(date, DateDayMapping::Day(Day::B), 1)];
+
+        let out = render(&routine, None, &notices, &[], &resolved);
+
+        assert!(out.contains("<div class=\"extra-class\">10:00 (Everyone)</div>"));
+    }
+}
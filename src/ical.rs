@@ -0,0 +1,329 @@
+//! Export a resolved semester's `ClassRoutine` and `Notice`s as an RFC 5545
+//! iCalendar (.ics) stream, so students can subscribe to their routine.
+
+use crate::{ClassInRoutine, ClassRoutine, DateDayMapping, Notice, Roll};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use std::collections::BTreeMap;
+
+/// Wall-clock start/end time for each period number.
+///
+/// Periods are otherwise just `u8`s with no wall-clock meaning, so this
+/// table is what lets [`to_ical`] place classes on an actual clock.
+#[derive(Debug, Clone, Default)]
+pub struct PeriodTimes(BTreeMap<u8, (NaiveTime, NaiveTime)>);
+
+impl PeriodTimes {
+    /// Create an empty period-to-clock-time table.
+    pub fn new() -> PeriodTimes {
+        PeriodTimes(BTreeMap::new())
+    }
+
+    /// Set the clock start/end time for `period`.
+    pub fn set(&mut self, period: u8, start: NaiveTime, end: NaiveTime) -> &mut PeriodTimes {
+        self.0.insert(period, (start, end));
+        self
+    }
+
+    /// Get the clock start/end time for `period`, if known.
+    pub fn get(&self, period: u8) -> Option<(NaiveTime, NaiveTime)> {
+        self.0.get(&period).copied()
+    }
+}
+
+/// FNV-1a 64-bit hash. Implemented locally rather than reaching for
+/// `std::collections::hash_map::DefaultHasher`, whose algorithm the standard
+/// library explicitly does not guarantee to stay the same across Rust
+/// releases — a course's UID has to stay stable across toolchain upgrades,
+/// or subscribers get every recurring event duplicated instead of updated.
+fn fnv1a_64(data: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    data.iter()
+        .fold(OFFSET_BASIS, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME))
+}
+
+fn stable_uid(parts: &[String]) -> String {
+    // Joined with a separator that can't occur in any part, so "AB","C" and
+    // "A","BC" don't collide.
+    let joined = parts.join("\u{1}");
+    format!("{:016x}@ruetian-common", fnv1a_64(joined.as_bytes()))
+}
+
+fn teacher_mailto(teacher: &str) -> String {
+    let local: String = teacher
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    format!("{}@ruet.ac.bd", local)
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Append `line` to `out`, folding it to 75 octets per line as RFC 5545
+/// section 3.1 requires, terminated with CRLF.
+fn fold_line(out: &mut String, line: &str) {
+    let mut bytes_on_line = 0;
+    let mut first = true;
+    for ch in line.chars() {
+        let ch_len = ch.len_utf8();
+        if !first && bytes_on_line + ch_len > 75 {
+            out.push_str("\r\n ");
+            // The continuation line already carries the leading space plus
+            // this character, so both must be reflected in the new count.
+            bytes_on_line = 1 + ch_len;
+        } else {
+            bytes_on_line += ch_len;
+        }
+        out.push(ch);
+        first = false;
+    }
+    out.push_str("\r\n");
+}
+
+fn class_event(
+    out: &mut String,
+    class: &ClassInRoutine,
+    date: NaiveDate,
+    period_times: &PeriodTimes,
+    dtstamp: DateTime<Utc>,
+) {
+    let uid = stable_uid(&[class.course.clone(), class.period.to_string(), date.to_string()]);
+    let mailto = teacher_mailto(&class.teacher);
+
+    fold_line(out, "BEGIN:VEVENT");
+    fold_line(out, &format!("UID:{}", uid));
+    fold_line(out, &format!("DTSTAMP:{}", dtstamp.format("%Y%m%dT%H%M%SZ")));
+    if let Some((start, end)) = period_times.get(class.period) {
+        fold_line(
+            out,
+            &format!("DTSTART:{}", NaiveDateTime::new(date, start).format("%Y%m%dT%H%M%S")),
+        );
+        fold_line(
+            out,
+            &format!("DTEND:{}", NaiveDateTime::new(date, end).format("%Y%m%dT%H%M%S")),
+        );
+    }
+    fold_line(out, &format!("SUMMARY:{}", escape_text(&class.course)));
+    fold_line(out, &format!("LOCATION:{}", escape_text(&class.class_room)));
+    fold_line(
+        out,
+        &format!("ORGANIZER;CN={}:mailto:{}", escape_text(&class.teacher), mailto),
+    );
+    fold_line(
+        out,
+        &format!("ATTENDEE;CN={}:mailto:{}", escape_text(&class.teacher), mailto),
+    );
+    if !class.comment.is_empty() {
+        fold_line(out, &format!("DESCRIPTION:{}", escape_text(&class.comment)));
+    }
+    fold_line(out, "END:VEVENT");
+}
+
+/// One concrete occurrence of a `Notice::ClassTest`, with its `day`/`cycle`
+/// already resolved to a `date`.
+struct ClassTestOccurrence<'a> {
+    date: NaiveDate,
+    period: u8,
+    course: &'a str,
+    teacher: &'a str,
+    extra_info: &'a str,
+}
+
+fn class_test_event(out: &mut String, occurrence: ClassTestOccurrence, period_times: &PeriodTimes, dtstamp: DateTime<Utc>) {
+    let ClassTestOccurrence {
+        date,
+        period,
+        course,
+        teacher,
+        extra_info,
+    } = occurrence;
+    let uid = stable_uid(&["test".to_owned(), course.to_owned(), date.to_string()]);
+    let mailto = teacher_mailto(teacher);
+
+    fold_line(out, "BEGIN:VEVENT");
+    fold_line(out, &format!("UID:{}", uid));
+    fold_line(out, &format!("DTSTAMP:{}", dtstamp.format("%Y%m%dT%H%M%SZ")));
+    if let Some((start, end)) = period_times.get(period) {
+        fold_line(
+            out,
+            &format!("DTSTART:{}", NaiveDateTime::new(date, start).format("%Y%m%dT%H%M%S")),
+        );
+        fold_line(
+            out,
+            &format!("DTEND:{}", NaiveDateTime::new(date, end).format("%Y%m%dT%H%M%S")),
+        );
+    }
+    fold_line(out, &format!("SUMMARY:{}", escape_text(&format!("Class Test: {}", course))));
+    fold_line(out, &format!("ORGANIZER;CN={}:mailto:{}", escape_text(teacher), mailto));
+    fold_line(out, &format!("DESCRIPTION:{}", escape_text(extra_info)));
+    fold_line(out, "END:VEVENT");
+}
+
+fn exam_event(out: &mut String, date: NaiveDate, course: &str, extra_info: &str, dtstamp: DateTime<Utc>) {
+    let uid = stable_uid(&["exam".to_owned(), course.to_owned(), date.to_string()]);
+
+    fold_line(out, "BEGIN:VEVENT");
+    fold_line(out, &format!("UID:{}", uid));
+    fold_line(out, &format!("DTSTAMP:{}", dtstamp.format("%Y%m%dT%H%M%SZ")));
+    fold_line(out, &format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+    fold_line(
+        out,
+        &format!("DTEND;VALUE=DATE:{}", (date + Duration::days(1)).format("%Y%m%d")),
+    );
+    fold_line(out, &format!("SUMMARY:{}", escape_text(&format!("Exam: {}", course))));
+    fold_line(out, &format!("DESCRIPTION:{}", escape_text(extra_info)));
+    fold_line(out, "END:VEVENT");
+}
+
+/// Render a resolved semester (the `(NaiveDate, DateDayMapping, cycle)`
+/// triples produced by `calendar::Calendar::iter_range`, or a caller-supplied
+/// equivalent) together with a `ClassRoutine` and `Notice`s as an RFC 5545
+/// iCalendar stream for `roll`.
+///
+/// `ClassTest` and `Exam` notices become their own `VEVENT`s with the
+/// syllabus in `DESCRIPTION`; `ClassOff`/holiday/weekend dates simply never
+/// produce a routine `VEVENT` since they never resolve to `DateDayMapping::Day`.
+pub fn to_ical<I>(
+    resolved: I,
+    routine: &ClassRoutine,
+    roll: Roll,
+    notices: &[Notice],
+    period_times: &PeriodTimes,
+    dtstamp: DateTime<Utc>,
+) -> String
+where
+    I: IntoIterator<Item = (NaiveDate, DateDayMapping, u8)>,
+{
+    let resolved: Vec<_> = resolved.into_iter().collect();
+    let mut out = String::new();
+
+    fold_line(&mut out, "BEGIN:VCALENDAR");
+    fold_line(&mut out, "VERSION:2.0");
+    fold_line(&mut out, "PRODID:-//ruetian//ruetian-common//EN");
+    fold_line(&mut out, "CALSCALE:GREGORIAN");
+
+    for (date, mapping, cycle) in &resolved {
+        if let DateDayMapping::Day(day) = mapping {
+            if let Some(classes) = routine.get(day) {
+                for class in classes {
+                    if class.would_sit_for(roll, *cycle) {
+                        class_event(&mut out, class, *date, period_times, dtstamp);
+                    }
+                }
+            }
+        }
+    }
+
+    for notice in notices {
+        match notice {
+            Notice::ClassTest {
+                day,
+                cycle,
+                period,
+                course,
+                teacher,
+                extra_info,
+            } => {
+                if let Some((date, ..)) = resolved
+                    .iter()
+                    .find(|(_, m, c)| matches!(m, DateDayMapping::Day(d) if d == day) && c == cycle)
+                {
+                    class_test_event(
+                        &mut out,
+                        ClassTestOccurrence {
+                            date: *date,
+                            period: *period,
+                            course,
+                            teacher,
+                            extra_info,
+                        },
+                        period_times,
+                        dtstamp,
+                    );
+                }
+            }
+            Notice::Exam {
+                date,
+                course,
+                extra_info,
+            } => exam_event(&mut out, *date, course, extra_info, dtstamp),
+            _ => {}
+        }
+    }
+
+    fold_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClassFrequency;
+
+    #[test]
+    fn fold_line_never_exceeds_75_octets_per_segment() {
+        let mut out = String::new();
+        fold_line(&mut out, &"a".repeat(200));
+
+        for segment in out.trim_end_matches("\r\n").split("\r\n") {
+            assert!(segment.len() <= 75, "segment was {} octets: {:?}", segment.len(), segment);
+        }
+    }
+
+    #[test]
+    fn fold_line_continuation_starts_with_a_space() {
+        let mut out = String::new();
+        fold_line(&mut out, &"a".repeat(200));
+
+        for segment in out.trim_end_matches("\r\n").split("\r\n").skip(1) {
+            assert!(segment.starts_with(' '));
+        }
+    }
+
+    #[test]
+    fn stable_uid_is_deterministic() {
+        let parts = vec!["EEE 2104".to_owned(), "1".to_owned(), "2024-01-01".to_owned()];
+        assert_eq!(stable_uid(&parts), stable_uid(&parts));
+    }
+
+    #[test]
+    fn stable_uid_distinguishes_part_boundaries() {
+        let joined_ab_c = stable_uid(&["AB".to_owned(), "C".to_owned()]);
+        let joined_a_bc = stable_uid(&["A".to_owned(), "BC".to_owned()]);
+        assert_ne!(joined_ab_c, joined_a_bc);
+    }
+
+    #[test]
+    fn class_event_formats_and_escapes_fields() {
+        let mut out = String::new();
+        let class = ClassInRoutine {
+            course: "EEE 2104".to_owned(),
+            teacher: "MFH".to_owned(),
+            period: 1,
+            class_room: "EEE, 201".to_owned(),
+            contact_hours: 3,
+            frequency: ClassFrequency::EveryCycleWithAll,
+            comment: String::new(),
+        };
+        let mut period_times = PeriodTimes::new();
+        period_times.set(1, NaiveTime::from_hms(8, 0, 0), NaiveTime::from_hms(8, 50, 0));
+        let dtstamp = DateTime::<Utc>::from_naive_utc_and_offset(
+            NaiveDateTime::new(NaiveDate::from_ymd(2024, 1, 1), NaiveTime::from_hms(0, 0, 0)),
+            Utc,
+        );
+
+        class_event(&mut out, &class, NaiveDate::from_ymd(2024, 1, 2), &period_times, dtstamp);
+
+        assert!(out.contains("SUMMARY:EEE 2104"));
+        assert!(out.contains("LOCATION:EEE\\, 201"));
+        assert!(out.contains("DTSTART:20240102T080000"));
+        assert!(out.contains("DTEND:20240102T085000"));
+    }
+}
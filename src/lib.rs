@@ -21,6 +21,13 @@ pub mod errors {
 
 use errors::*;
 
+pub mod calendar;
+pub mod catalog;
+pub mod html;
+pub mod ical;
+
+use catalog::CourseCatalog;
+
 /// Department of a RUETian.
 #[derive(
     Serialize, Deserialize, Debug, Hash, Clone, Copy, Eq, PartialEq, TryFromPrimitive, Display,
@@ -50,16 +57,13 @@ pub enum Department {
 }
 
 impl Department {
-    /// Get official and colloquial name of a course.
-    pub fn get_course_name(self, code: &str) -> Result<(&'static str, &'static str)> {
-        use Department::*;
-        match self {
-            EEE => match code {
-                "EEE 2100" => Ok(("Electrical Shop Practice", "Electrical Shop")),
-                invalid => Err(format!("No course '{}' available for {}", invalid, self).into()),
-            },
-            _ => Err(format!("No course available for {}", self).into()),
-        }
+    /// Get official and colloquial name of a course by looking it up in
+    /// `catalog`. Fails if no catalog is given, or the code isn't in it.
+    pub fn get_course_name(self, code: &str, catalog: Option<&CourseCatalog>) -> Result<(String, String)> {
+        let catalog = catalog
+            .ok_or_else(|| format!("No course catalog available to look up '{}' for {}", code, self))?;
+        let info = catalog.get(self, code)?;
+        Ok((info.official_name.clone(), info.colloquial_name.clone()))
     }
 }
 
@@ -401,6 +405,36 @@ impl HolidaySpan {
     }
 }
 
+/// Describes how a `Holiday` repeats across years.
+#[derive(Serialize, Deserialize, Debug, Hash, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum Recurrence {
+    /// Repeats every year on the same month/day as the holiday's own span.
+    Annual,
+
+    /// Repeats every year on a fixed month/day, independent of the span
+    /// stored on the holiday.
+    #[serde(rename_all = "camelCase")]
+    AnnualMonthDay {
+        /// Month of recurrence (1-12).
+        month: u32,
+        /// Day of recurrence.
+        day: u32,
+    },
+}
+
+/// Shift `date` onto `year`, keeping its month/day. Feb 29 clamps to Feb 28
+/// in years that aren't leap years.
+fn shift_year(date: NaiveDate, year: i32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, date.month(), date.day()).or_else(|| {
+        if date.month() == 2 && date.day() == 29 {
+            NaiveDate::from_ymd_opt(year, 2, 28)
+        } else {
+            None
+        }
+    })
+}
+
 /// Describes an official holiday in RUET.
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -411,6 +445,58 @@ pub struct Holiday {
     /// Start date of this holiady.
     #[serde(flatten)]
     pub span: HolidaySpan,
+
+    /// If this holiday repeats every year, how.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<Recurrence>,
+}
+
+impl Holiday {
+    /// Materialize every concrete occurrence of this holiday that overlaps
+    /// `range` (inclusive), expanding `recurrence` into one `HolidaySpan`
+    /// per matched year. A non-recurring holiday yields at most its own
+    /// span.
+    pub fn occurrences(&self, range: (NaiveDate, NaiveDate)) -> Vec<HolidaySpan> {
+        let candidate_years = (range.0.year() - 1)..=(range.1.year() + 1);
+
+        match self.recurrence {
+            None => {
+                if self.span.start() <= range.1 && self.span.end() >= range.0 {
+                    vec![self.span]
+                } else {
+                    vec![]
+                }
+            }
+            Some(Recurrence::Annual) => {
+                let duration = self.span.end() - self.span.start();
+                candidate_years
+                    .filter_map(|year| shift_year(self.span.start(), year))
+                    .map(|from| {
+                        let to = from + duration;
+                        if from == to {
+                            HolidaySpan::SingleDay { on: from }
+                        } else {
+                            HolidaySpan::MultiDays { from, to }
+                        }
+                    })
+                    .filter(|span| span.start() <= range.1 && span.end() >= range.0)
+                    .collect()
+            }
+            Some(Recurrence::AnnualMonthDay { month, day }) => candidate_years
+                .filter_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+                .map(|on| HolidaySpan::SingleDay { on })
+                .filter(|span| span.start() <= range.1 && span.end() >= range.0)
+                .collect(),
+        }
+    }
+
+    /// Check whether `date` falls within this holiday, across all of its
+    /// recurrences.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.occurrences((date, date))
+            .iter()
+            .any(|span| span.contains(date))
+    }
 }
 
 /// Describes a possible date-to-day relation.
@@ -484,11 +570,32 @@ A:
                 span: HolidaySpan::MultiDays {
                     from: NaiveDate::from_ymd(2020, 1, 1),
                     to: NaiveDate::from_ymd(2020, 1, 1)
-                }
+                },
+                recurrence: None
             };
 
             println!("{:#?}", holiady);
             println!("{}", yaml::to_string(&holiady).unwrap());
         }
+
+        it "should repeat an annual holiday every year" {
+            let victory_day = Holiday {
+                r#for: "Victory Day".to_string(),
+                span: HolidaySpan::SingleDay {
+                    on: NaiveDate::from_ymd(2020, 12, 16)
+                },
+                recurrence: Some(Recurrence::Annual)
+            };
+
+            assert!(victory_day.contains(NaiveDate::from_ymd(2020, 12, 16)));
+            assert!(victory_day.contains(NaiveDate::from_ymd(2021, 12, 16)));
+            assert!(!victory_day.contains(NaiveDate::from_ymd(2021, 12, 17)));
+
+            let occurrences = victory_day.occurrences((
+                NaiveDate::from_ymd(2020, 1, 1),
+                NaiveDate::from_ymd(2022, 12, 31)
+            ));
+            assert_eq!(occurrences.len(), 3);
+        }
     }
 }